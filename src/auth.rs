@@ -0,0 +1,118 @@
+use reqwest::header::HeaderValue;
+
+/// A credential scoped to a host suffix, mirroring Deno's `DENO_AUTH_TOKENS`.
+#[derive(Debug, Clone)]
+enum Credential {
+    Bearer(String),
+    Basic { username: String, password: String },
+}
+
+impl Credential {
+    /// Renders the credential as an `Authorization` header value.
+    fn header_value(&self) -> Option<HeaderValue> {
+        let raw = match self {
+            Credential::Bearer(token) => format!("Bearer {token}"),
+            Credential::Basic { username, password } => {
+                use base64::Engine;
+                let encoded = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{username}:{password}"));
+                format!("Basic {encoded}")
+            }
+        };
+        HeaderValue::from_str(&raw).ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AuthToken {
+    host: String,
+    credential: Credential,
+}
+
+/// A set of per-host credentials injected into matching outgoing requests.
+#[derive(Debug, Clone, Default)]
+pub struct AuthTokens {
+    tokens: Vec<AuthToken>,
+}
+
+impl AuthTokens {
+    /// Parses a `;`-separated list of `token@host` or `user:password@host`
+    /// entries, as produced by the `CRAWL_AUTH_TOKENS` environment variable.
+    pub fn new(input: Option<&str>) -> Self {
+        let mut tokens = Vec::new();
+        if let Some(input) = input {
+            for entry in input.split(';') {
+                let entry = entry.trim();
+                if entry.is_empty() {
+                    continue;
+                }
+                match entry.rsplit_once('@') {
+                    Some((credential, host)) => {
+                        let credential = match credential.split_once(':') {
+                            Some((username, password)) => Credential::Basic {
+                                username: username.to_owned(),
+                                password: password.to_owned(),
+                            },
+                            None => Credential::Bearer(credential.to_owned()),
+                        };
+                        tokens.push(AuthToken {
+                            host: host.to_ascii_lowercase(),
+                            credential,
+                        });
+                    }
+                    None => log::warn!("Ignoring malformed auth token entry: {}", entry),
+                }
+            }
+        }
+        Self { tokens }
+    }
+
+    /// Loads tokens from the `CRAWL_AUTH_TOKENS` environment variable.
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("CRAWL_AUTH_TOKENS").ok().as_deref())
+    }
+
+    /// The `Authorization` header value for `host`, if `host` equals a
+    /// credential's host or is a subdomain of it.
+    pub fn authorization(&self, host: &str) -> Option<HeaderValue> {
+        let host = host.to_ascii_lowercase();
+        self.tokens
+            .iter()
+            .find(|token| host == token.host || host.ends_with(&format!(".{}", token.host)))
+            .and_then(|token| token.credential.header_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_token_matches_host_and_subdomains() {
+        let tokens = AuthTokens::new(Some("secret@staging.monzo.com"));
+        assert_eq!(
+            Some(HeaderValue::from_static("Bearer secret")),
+            tokens.authorization("staging.monzo.com")
+        );
+        assert_eq!(
+            Some(HeaderValue::from_static("Bearer secret")),
+            tokens.authorization("api.staging.monzo.com")
+        );
+        assert_eq!(None, tokens.authorization("monzo.com"));
+    }
+
+    #[test]
+    fn basic_credentials_are_base64_encoded() {
+        let tokens = AuthTokens::new(Some("alice:hunter2@example.com"));
+        assert_eq!(
+            Some(HeaderValue::from_static("Basic YWxpY2U6aHVudGVyMg==")),
+            tokens.authorization("example.com")
+        );
+    }
+
+    #[test]
+    fn empty_input_matches_nothing() {
+        let tokens = AuthTokens::new(None);
+        assert_eq!(None, tokens.authorization("example.com"));
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::header::{HeaderMap, HeaderName, CACHE_CONTROL, ETAG, EXPIRES, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// A cached response body together with the validators and freshness metadata
+/// needed to revalidate it on a later crawl.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Unix seconds at which the response was stored.
+    stored_at: u64,
+    /// `max-age` from `Cache-Control`, in seconds.
+    max_age: Option<u64>,
+    /// `Expires` header parsed to Unix seconds.
+    expires: Option<u64>,
+}
+
+impl CacheEntry {
+    /// Builds an entry from a response's headers and decoded body, capturing the
+    /// `ETag`/`Last-Modified` validators and any freshness lifetime.
+    pub fn from_response(headers: &HeaderMap, body: String) -> Self {
+        let max_age = CacheControl::parse(headers).max_age;
+        let expires = header_string(headers, EXPIRES)
+            .and_then(|v| httpdate::parse_http_date(&v).ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        Self {
+            body,
+            etag: header_string(headers, ETAG),
+            last_modified: header_string(headers, LAST_MODIFIED),
+            stored_at: now_secs(),
+            max_age,
+            expires,
+        }
+    }
+
+    /// Whether the entry is still fresh and can be served without a network
+    /// round-trip, per its `Cache-Control: max-age` or `Expires`.
+    pub fn is_fresh(&self) -> bool {
+        let now = now_secs();
+        if let Some(max_age) = self.max_age {
+            return now.saturating_sub(self.stored_at) < max_age;
+        }
+        if let Some(expires) = self.expires {
+            return now < expires;
+        }
+        false
+    }
+}
+
+/// Parsed subset of the `Cache-Control` response header.
+#[derive(Debug, Default, Clone)]
+pub struct CacheControl {
+    pub no_store: bool,
+    pub max_age: Option<u64>,
+}
+
+impl CacheControl {
+    pub fn parse(headers: &HeaderMap) -> Self {
+        let mut cc = CacheControl::default();
+        let value = match headers.get(CACHE_CONTROL).and_then(|v| v.to_str().ok()) {
+            Some(value) => value,
+            None => return cc,
+        };
+        for directive in value.split(',') {
+            let directive = directive.trim();
+            if directive.eq_ignore_ascii_case("no-store") {
+                cc.no_store = true;
+            } else if let Some(rest) = directive.strip_prefix("max-age=") {
+                cc.max_age = rest.trim().parse().ok();
+            }
+        }
+        cc
+    }
+
+    /// Whether a response carrying these directives may be persisted.
+    pub fn is_cachable(&self) -> bool {
+        !self.no_store
+    }
+}
+
+/// A content cache stored as one JSON file per URL under `root`.
+pub struct DiskCache {
+    root: PathBuf,
+}
+
+impl DiskCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Default on-disk location under the system temp directory.
+    pub fn default_location() -> Self {
+        Self::new(std::env::temp_dir().join("crawl-rs-cache"))
+    }
+
+    /// Looks up the cached entry for `url`, if one has been stored.
+    pub fn get(&self, url: &str) -> Option<CacheEntry> {
+        let bytes = fs::read(self.path_for(url)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Persists `entry` for `url`, creating the cache directory if needed.
+    pub fn put(&self, url: &str, entry: &CacheEntry) -> Result<()> {
+        fs::create_dir_all(&self.root)?;
+        fs::write(self.path_for(url), serde_json::to_vec(entry)?)?;
+        Ok(())
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.root.join(format!("{:x}.json", hasher.finish()))
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_owned())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(HeaderName, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(name.clone(), value.parse().unwrap());
+        }
+        map
+    }
+
+    mod cache_control {
+        use super::*;
+
+        #[test]
+        fn parses_directives() {
+            let cc = CacheControl::parse(&headers(&[(CACHE_CONTROL, "public, max-age=600")]));
+            assert_eq!(Some(600), cc.max_age);
+            assert!(cc.is_cachable());
+        }
+
+        #[test]
+        fn no_store_is_not_cachable() {
+            let cc = CacheControl::parse(&headers(&[(CACHE_CONTROL, "no-store")]));
+            assert!(!cc.is_cachable());
+        }
+    }
+
+    mod entry {
+        use super::*;
+
+        #[test]
+        fn captures_validators() {
+            let entry = CacheEntry::from_response(
+                &headers(&[
+                    (ETAG, "\"abc\""),
+                    (LAST_MODIFIED, "Wed, 21 Oct 2015 07:28:00 GMT"),
+                ]),
+                "<html></html>".to_owned(),
+            );
+            assert_eq!(Some("\"abc\"".to_owned()), entry.etag);
+            assert_eq!(
+                Some("Wed, 21 Oct 2015 07:28:00 GMT".to_owned()),
+                entry.last_modified
+            );
+        }
+
+        #[test]
+        fn fresh_within_max_age() {
+            let entry = CacheEntry::from_response(
+                &headers(&[(CACHE_CONTROL, "max-age=600")]),
+                String::new(),
+            );
+            assert!(entry.is_fresh());
+        }
+
+        #[test]
+        fn stale_without_lifetime() {
+            let entry = CacheEntry::from_response(&HeaderMap::new(), String::new());
+            assert!(!entry.is_fresh());
+        }
+    }
+
+    mod disk {
+        use super::*;
+
+        #[test]
+        fn round_trips() {
+            let cache = DiskCache::new(std::env::temp_dir().join("crawl-rs-cache-test-roundtrip"));
+            let entry = CacheEntry::from_response(
+                &headers(&[(ETAG, "\"v1\""), (CACHE_CONTROL, "max-age=60")]),
+                "cached body".to_owned(),
+            );
+            cache.put("https://example.com/a", &entry).unwrap();
+
+            let loaded = cache.get("https://example.com/a").unwrap();
+            assert_eq!("cached body", loaded.body);
+            assert_eq!(Some("\"v1\"".to_owned()), loaded.etag);
+            assert!(cache.get("https://example.com/missing").is_none());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use url::Url;
+
+/// How crawl results are rendered to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputMode {
+    /// Human-readable log lines.
+    Human,
+    /// One JSON object per line for machine consumption.
+    Json,
+}
+
+/// A single crawl event, emitted one-per-line in [`OutputMode::Json`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CrawlEvent {
+    /// A URL was fetched, carrying its final HTTP status and timing.
+    Visited {
+        url: Url,
+        status: u16,
+        elapsed_ms: u128,
+    },
+    /// In-scope links discovered on a fetched page.
+    Discovered { url: Url, links: Vec<Url> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visited_serializes_as_jsonl() {
+        let event = CrawlEvent::Visited {
+            url: Url::parse("https://community.monzo.com/").unwrap(),
+            status: 200,
+            elapsed_ms: 42,
+        };
+        assert_eq!(
+            r#"{"event":"visited","url":"https://community.monzo.com/","status":200,"elapsed_ms":42}"#,
+            serde_json::to_string(&event).unwrap()
+        );
+    }
+
+    #[test]
+    fn discovered_serializes_as_jsonl() {
+        let event = CrawlEvent::Discovered {
+            url: Url::parse("https://community.monzo.com/").unwrap(),
+            links: vec![Url::parse("https://community.monzo.com/tos").unwrap()],
+        };
+        assert_eq!(
+            r#"{"event":"discovered","url":"https://community.monzo.com/","links":["https://community.monzo.com/tos"]}"#,
+            serde_json::to_string(&event).unwrap()
+        );
+    }
+}
@@ -1,11 +1,23 @@
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 
+mod auth;
+mod cache;
+mod event;
 mod link_parser;
+mod rate_limit;
 mod requester;
+mod robots;
+mod scope;
 
+pub use auth::*;
+pub use cache::*;
+pub use event::*;
 pub use link_parser::*;
+pub use rate_limit::*;
 pub use requester::*;
+pub use robots::*;
+pub use scope::*;
 
 pub type Result<T> = eyre::Result<T>;
 pub type AtomicSet = Arc<Mutex<HashSet<String>>>;
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::{AtomicSet, Result};
+use crate::{AtomicSet, CrawlEvent, OutputMode, Result, RobotsGate, Scope};
 use flume::{Receiver, Sender};
 use scraper::{Html, Selector};
 use tokio::time::timeout;
@@ -8,25 +8,38 @@ use url::Url;
 
 pub struct LinkParser<'a> {
     subdomain: &'a str,
+    scope: Scope,
+    output: OutputMode,
+    robots: RobotsGate,
     visited_paths: AtomicSet,
-    html_rx: Receiver<String>,
+    html_rx: Receiver<(Url, String)>,
     url_tx: Sender<Url>,
+    event_tx: Sender<CrawlEvent>,
     timeout: Duration,
 }
 
 impl<'a> LinkParser<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         subdomain: &'a str,
+        scope: Scope,
+        output: OutputMode,
+        robots: RobotsGate,
         visited_paths: AtomicSet,
-        html_rx: Receiver<String>,
+        html_rx: Receiver<(Url, String)>,
         url_tx: Sender<Url>,
+        event_tx: Sender<CrawlEvent>,
         timeout: Duration,
     ) -> Self {
         Self {
             subdomain,
+            scope,
+            output,
+            robots,
             visited_paths,
             html_rx,
             url_tx,
+            event_tx,
             timeout,
         }
     }
@@ -35,9 +48,9 @@ impl<'a> LinkParser<'a> {
     pub async fn run(&self) -> Result<()> {
         loop {
             log::info!("Link Parser: Waiting to recv html");
-            let html = match timeout(self.timeout, self.html_rx.recv_async()).await {
+            let (page_url, html) = match timeout(self.timeout, self.html_rx.recv_async()).await {
                 Ok(recv) => match recv {
-                    Ok(url) => url,
+                    Ok(page) => page,
                     Err(_) => {
                         log::info!("Link Parser: Html channel dropped");
                         return Ok(());
@@ -48,11 +61,27 @@ impl<'a> LinkParser<'a> {
                     return Ok(());
                 }
             };
-            let urls = parse_urls(&html, self.subdomain);
+            // Relative hrefs resolve against the page they were found on, which
+            // may differ from the seed host under `subdomains`/`domain` scope.
+            let page_host = page_url.host_str().unwrap_or(self.subdomain).to_owned();
+            let urls = parse_urls(&html, self.subdomain, &page_host, self.scope, self.output);
+            if self.output == OutputMode::Json {
+                let event = CrawlEvent::Discovered {
+                    url: page_url,
+                    links: urls.clone(),
+                };
+                if let Err(e) = self.event_tx.send(event) {
+                    log::warn!("Error sending crawl event: {:?}", e);
+                }
+            }
             for url in urls.into_iter() {
                 if url.path().ends_with(".pdf") || url.path().ends_with(".mp3") {
                     continue;
                 }
+                if !self.robots.is_allowed(&url).await {
+                    log::info!("Link Parser: {} disallowed by robots.txt", url);
+                    continue;
+                }
                 let path = url.path().trim_end_matches('/').to_owned();
                 let mut visited_paths = self.visited_paths.lock().await;
                 if visited_paths.contains(&path) {
@@ -70,7 +99,13 @@ impl<'a> LinkParser<'a> {
 }
 
 /// Parses Html string into Vector of Urls based on nested anchor tags
-fn parse_urls(html: &str, original_subdomain: &str) -> Vec<Url> {
+fn parse_urls(
+    html: &str,
+    original_subdomain: &str,
+    page_host: &str,
+    scope: Scope,
+    output: OutputMode,
+) -> Vec<Url> {
     let mut urls: Vec<Url> = Vec::new();
     let documents = parse_html(html);
 
@@ -78,14 +113,14 @@ fn parse_urls(html: &str, original_subdomain: &str) -> Vec<Url> {
     for document in documents {
         for link in document.select(&anchor_selector) {
             if let Some(href) = link.value().attr("href") {
-                let href = normalize_href(href, original_subdomain);
+                let href = normalize_href(href, page_host);
                 match Url::parse(&href) {
                     Ok(url) => {
-                        println!("Found URL: {}", url);
-                        if let Some(subdomain) = url.domain() {
-                            if subdomain == original_subdomain {
-                                urls.push(url);
-                            }
+                        if output == OutputMode::Human {
+                            println!("Found URL: {}", url);
+                        }
+                        if scope.matches(original_subdomain, &url) {
+                            urls.push(url);
                         }
                     }
                     Err(e) => {
@@ -155,16 +190,21 @@ mod tests {
 
             let (url_tx, url_rx) = flume::unbounded();
             let (html_tx, html_rx) = flume::unbounded();
+            let (event_tx, _event_rx) = flume::unbounded();
 
             let html = std::fs::read_to_string("tests/fixtures/community.monzo.com.html").unwrap();
-            html_tx.send(html).unwrap();
+            html_tx.send((url, html)).unwrap();
             drop(html_tx);
 
             let link_parser = LinkParser::new(
                 &original_subdomain,
+                Scope::Host,
+                OutputMode::Human,
+                RobotsGate::new("crawl-rs/test".to_owned(), false, Duration::from_secs(1)),
                 Arc::clone(&seen),
                 html_rx,
                 url_tx,
+                event_tx,
                 Duration::from_secs(0),
             );
             let link_parser_handle = tokio::spawn(async move {
@@ -221,14 +261,38 @@ mod tests {
                 <a href='tel:+448008021281'></a>\
                 <a href='../blog/2017/03/10/transparent-by-default/'></a>\
                 </body></html>";
-            let urls = parse_urls(html, "monzo.com");
+            let urls = parse_urls(html, "monzo.com", "monzo.com", Scope::Host, OutputMode::Human);
             assert_eq!(6, urls.len())
         }
 
+        #[test]
+        fn relative_href_resolves_against_page_host() {
+            // A relative link found on an in-scope non-seed host must be
+            // resolved against that host, not the seed.
+            let html = "<html><body><a href='/help'></a></body></html>";
+            let urls = parse_urls(
+                html,
+                "community.monzo.com",
+                "www.community.monzo.com",
+                Scope::Subdomains,
+                OutputMode::Human,
+            );
+            assert_eq!(
+                vec!["https://www.community.monzo.com/help"],
+                urls.iter().map(|url| url.as_str()).collect::<Vec<_>>()
+            );
+        }
+
         #[test]
         fn community_monzo_com() {
             let html = std::fs::read_to_string("tests/fixtures/community.monzo.com.html").unwrap();
-            let urls = parse_urls(&html, "community.monzo.com");
+            let urls = parse_urls(
+                &html,
+                "community.monzo.com",
+                "community.monzo.com",
+                Scope::Host,
+                OutputMode::Human,
+            );
             assert_eq!(15, urls.len())
         }
     }
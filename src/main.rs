@@ -1,6 +1,6 @@
 use clap::Parser;
 use color_eyre::eyre;
-use crawl::{AtomicSet, LinkParser, Requester};
+use crawl::{AtomicSet, LinkParser, OutputMode, RateLimiter, Requester, RobotsGate, Scope};
 use std::{collections::HashSet, sync::Arc, time::Duration};
 use tokio::{sync::Mutex, try_join};
 use url::Url;
@@ -19,6 +19,34 @@ pub struct Args {
     /// http request timeout in seconds
     #[clap(short, long, default_value = "5")]
     timeout: u64,
+
+    /// Maximum number of redirects to follow before giving up
+    #[clap(long, default_value = "10")]
+    max_redirects: usize,
+
+    /// Which hosts stay in scope relative to the seed URL
+    #[clap(long, value_enum, default_value_t = Scope::Host)]
+    scope: Scope,
+
+    /// Output format: human-readable log lines or one JSON object per line
+    #[clap(short, long, value_enum, default_value_t = OutputMode::Human)]
+    output: OutputMode,
+
+    /// User-Agent header sent with each request
+    #[clap(long, default_value = concat!("crawl-rs/", env!("CARGO_PKG_VERSION")))]
+    user_agent: String,
+
+    /// Obey each host's robots.txt rules and crawl-delay
+    #[clap(long, default_value_t = true, action = clap::ArgAction::Set)]
+    respect_robots: bool,
+
+    /// Minimum delay between requests to the same host, in milliseconds
+    #[clap(long)]
+    delay_ms: Option<u64>,
+
+    /// Maximum requests per second per host
+    #[clap(long)]
+    requests_per_sec: Option<f64>,
 }
 
 #[tokio::main]
@@ -30,6 +58,22 @@ async fn main() -> eyre::Result<()> {
     let url = args.url;
     let timeout = Duration::from_secs(args.timeout);
     let concurrency = args.concurrency;
+    let max_redirects = args.max_redirects;
+    let scope = args.scope;
+    let output = args.output;
+    let user_agent = args.user_agent;
+    let respect_robots = args.respect_robots;
+
+    // The stricter of the two pacing options wins.
+    let mut min_delay = Duration::ZERO;
+    if let Some(ms) = args.delay_ms {
+        min_delay = min_delay.max(Duration::from_millis(ms));
+    }
+    if let Some(rps) = args.requests_per_sec {
+        if rps > 0.0 {
+            min_delay = min_delay.max(Duration::from_secs_f64(1.0 / rps));
+        }
+    }
 
     let original_subdomain = match url.domain() {
         Some(d) => d.to_owned(),
@@ -42,11 +86,41 @@ async fn main() -> eyre::Result<()> {
 
     let (url_tx, url_rx) = flume::unbounded();
     let (html_tx, html_rx) = flume::unbounded();
+    let (event_tx, event_rx) = flume::unbounded();
 
     url_tx.send_async(url.clone()).await?;
 
+    let robots = RobotsGate::new(user_agent.clone(), respect_robots, timeout);
+    let rate_limiter = RateLimiter::new(min_delay);
+
+    // A single writer task drains the event channel so JSON lines are emitted in
+    // a consistent order regardless of requester/parser interleaving.
+    let writer_handle = tokio::spawn(async move {
+        while let Ok(event) = event_rx.recv_async().await {
+            match serde_json::to_string(&event) {
+                Ok(line) => println!("{}", line),
+                Err(e) => log::warn!("Error serializing crawl event: {:?}", e),
+            }
+        }
+    });
+
+    let requester_seen = Arc::clone(&seen);
+    let requester_event_tx = event_tx.clone();
+    let requester_robots = robots.clone();
     let requester_handle = tokio::spawn(async move {
-        let requester = Requester::new(url_rx, html_tx, concurrency, timeout);
+        let requester = Requester::new(
+            url_rx,
+            html_tx,
+            requester_event_tx,
+            output,
+            concurrency,
+            timeout,
+            max_redirects,
+            user_agent,
+            Arc::clone(&requester_seen),
+            requester_robots,
+            rate_limiter,
+        );
         if let Err(e) = requester.run().await {
             log::error!("{}", e);
         }
@@ -55,9 +129,13 @@ async fn main() -> eyre::Result<()> {
     let link_parser_handle = tokio::spawn(async move {
         let link_parser = LinkParser::new(
             &original_subdomain,
+            scope,
+            output,
+            robots,
             Arc::clone(&seen),
             html_rx,
             url_tx,
+            event_tx,
             timeout,
         );
         if let Err(e) = link_parser.run().await {
@@ -66,6 +144,7 @@ async fn main() -> eyre::Result<()> {
     });
 
     try_join!(requester_handle, link_parser_handle)?;
+    writer_handle.await?;
     Ok(())
 }
 
@@ -0,0 +1,95 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use tokio::{sync::Mutex, time::Instant};
+use url::Url;
+
+/// Per-host pacing so a single host is never contacted faster than its delay,
+/// while requests to different hosts still proceed concurrently.
+#[derive(Clone)]
+pub struct RateLimiter {
+    min_delay: Duration,
+    last_request: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl RateLimiter {
+    pub fn new(min_delay: Duration) -> Self {
+        Self {
+            min_delay,
+            last_request: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Awaits until `url`'s host may be contacted again, honoring the stricter
+    /// of the configured delay and any robots.txt `Crawl-delay`.
+    pub async fn throttle(&self, url: &Url, crawl_delay: Option<Duration>) {
+        let delay = match crawl_delay {
+            Some(crawl_delay) => crawl_delay.max(self.min_delay),
+            None => self.min_delay,
+        };
+        if delay.is_zero() {
+            return;
+        }
+        let host = match url.host_str() {
+            Some(host) => host.to_owned(),
+            None => return,
+        };
+
+        // Reserve this request's slot under the lock, then sleep outside it so
+        // other hosts are not blocked.
+        let ready_at = {
+            let mut last_request = self.last_request.lock().await;
+            let now = Instant::now();
+            let ready_at = match last_request.get(&host) {
+                Some(previous) => (*previous + delay).max(now),
+                None => now,
+            };
+            last_request.insert(host, ready_at);
+            ready_at
+        };
+        tokio::time::sleep_until(ready_at).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn same_host_is_spaced_by_min_delay() {
+        let limiter = RateLimiter::new(Duration::from_millis(100));
+        let url = url("https://example.com/a");
+
+        let start = Instant::now();
+        limiter.throttle(&url, None).await;
+        limiter.throttle(&url, None).await;
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn different_hosts_are_not_serialized() {
+        let limiter = RateLimiter::new(Duration::from_millis(100));
+
+        let start = Instant::now();
+        limiter.throttle(&url("https://a.example/"), None).await;
+        limiter.throttle(&url("https://b.example/"), None).await;
+
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn crawl_delay_wins_when_stricter() {
+        let limiter = RateLimiter::new(Duration::from_millis(10));
+        let url = url("https://example.com/");
+
+        let start = Instant::now();
+        limiter.throttle(&url, Some(Duration::from_millis(500))).await;
+        limiter.throttle(&url, Some(Duration::from_millis(500))).await;
+
+        assert!(start.elapsed() >= Duration::from_millis(500));
+    }
+}
@@ -1,31 +1,132 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::Result;
+use crate::{
+    AtomicSet, AuthTokens, CacheControl, CacheEntry, CrawlEvent, DiskCache, OutputMode, RateLimiter,
+    Result, RobotsGate,
+};
 use flume::{Receiver, Sender};
+use reqwest::header::{AUTHORIZATION, IF_MODIFIED_SINCE, IF_NONE_MATCH, LOCATION};
 use url::Url;
 
 pub struct Requester {
     client: reqwest::Client,
     url_rx: Receiver<Url>,
-    html_tx: Sender<String>,
+    html_tx: Sender<(Url, String)>,
+    event_tx: Sender<CrawlEvent>,
+    output: OutputMode,
     concurrency: usize,
+    max_redirects: usize,
+    visited_paths: AtomicSet,
+    cache: DiskCache,
+    auth: AuthTokens,
+    robots: RobotsGate,
+    rate_limiter: RateLimiter,
 }
 
 impl Requester {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         url_rx: Receiver<Url>,
-        html_tx: Sender<String>,
+        html_tx: Sender<(Url, String)>,
+        event_tx: Sender<CrawlEvent>,
+        output: OutputMode,
         concurrency: usize,
         timeout: Duration,
+        max_redirects: usize,
+        user_agent: String,
+        visited_paths: AtomicSet,
+        robots: RobotsGate,
+        rate_limiter: RateLimiter,
     ) -> Self {
         Self {
-            client: reqwest::Client::builder().timeout(timeout).build().unwrap(),
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .redirect(reqwest::redirect::Policy::none())
+                .user_agent(user_agent)
+                .build()
+                .unwrap(),
             url_rx,
             html_tx,
+            event_tx,
+            output,
             concurrency,
+            max_redirects,
+            visited_paths,
+            cache: DiskCache::default_location(),
+            auth: AuthTokens::from_env(),
+            robots,
+            rate_limiter,
         }
     }
 
+    /// Follows 3xx `Location` redirects manually starting from `start`, stopping
+    /// after `max_redirects` hops to guard against loops. Returns the final URL
+    /// and its non-redirect response. Conditional-GET validators from `cached`
+    /// are only attached to the first hop.
+    async fn follow_redirects(
+        &self,
+        start: Url,
+        cached: &Option<CacheEntry>,
+    ) -> Option<(Url, reqwest::Response)> {
+        let mut current = start.clone();
+        for hop in 0..=self.max_redirects {
+            // Pace per host, combining the configured delay with robots.txt.
+            let crawl_delay = self.robots.crawl_delay(&current).await;
+            self.rate_limiter.throttle(&current, crawl_delay).await;
+
+            let mut request = self.client.get(current.clone());
+            if let Some(host) = current.host_str() {
+                if let Some(value) = self.auth.authorization(host) {
+                    request = request.header(AUTHORIZATION, value);
+                }
+            }
+            if hop == 0 {
+                if let Some(entry) = cached {
+                    if let Some(etag) = &entry.etag {
+                        request = request.header(IF_NONE_MATCH, etag);
+                    }
+                    if let Some(last_modified) = &entry.last_modified {
+                        request = request.header(IF_MODIFIED_SINCE, last_modified);
+                    }
+                }
+            }
+
+            let response = match request.send().await {
+                Ok(res) => res,
+                Err(e) => {
+                    log::warn!("URL {:?} returned status {:?}", e.url(), e.status());
+                    return None;
+                }
+            };
+
+            // 304 shares the 3xx range but is a revalidation answer, not a hop.
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED
+                || !response.status().is_redirection()
+            {
+                return Some((current, response));
+            }
+
+            let location = match response.headers().get(LOCATION).and_then(|v| v.to_str().ok()) {
+                Some(location) => location,
+                None => {
+                    log::warn!("Redirect from {} without a Location header", current);
+                    return None;
+                }
+            };
+            let next = match current.join(location) {
+                Ok(next) => next,
+                Err(e) => {
+                    log::warn!("Could not resolve redirect location {}: {:?}", location, e);
+                    return None;
+                }
+            };
+            log::info!("Redirect {} -> {}", current, next);
+            current = next;
+        }
+        log::warn!("Too many redirects for {}", start);
+        None
+    }
+
     pub async fn run(&self) -> Result<()> {
         use futures::stream::StreamExt;
 
@@ -33,21 +134,68 @@ impl Requester {
         self.url_rx
             .stream()
             .for_each_concurrent(self.concurrency, |url| async move {
-                println!("Visited URL: {}", url);
-                let response = match self.client.get(url).send().await {
-                    Ok(res) => match res.error_for_status() {
-                        Ok(res) => res,
-                        Err(e) => {
-                            log::warn!("URL {:?} returned status {:?}", e.url(), e.status());
-                            return;
+                if self.output == OutputMode::Human {
+                    println!("Visited URL: {}", url);
+                }
+                let url_str = url.as_str().to_owned();
+
+                // Serve from cache without a network round-trip while still fresh.
+                let cached = self.cache.get(&url_str);
+                if let Some(entry) = &cached {
+                    if entry.is_fresh() {
+                        log::info!("Cache hit (fresh) for {}", url_str);
+                        // Served from cache: no round-trip, so report zero elapsed.
+                        self.emit_visited(&url, reqwest::StatusCode::OK, Duration::ZERO);
+                        if let Err(e) =
+                            self.html_tx.send_async((url.clone(), entry.body.clone())).await
+                        {
+                            log::warn!("Error sending html to channel: {:?}", e);
                         }
-                    },
+                        return;
+                    }
+                }
+
+                // Otherwise revalidate, following any redirects ourselves.
+                let started = Instant::now();
+                let (final_url, response) = match self.follow_redirects(url, &cached).await {
+                    Some(pair) => pair,
+                    None => return,
+                };
+                self.emit_visited(&final_url, response.status(), started.elapsed());
+
+                if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                    match cached {
+                        Some(entry) => {
+                            log::info!("304 Not Modified, reusing cached body for {}", url_str);
+                            if let Err(e) =
+                                self.html_tx.send_async((final_url, entry.body)).await
+                            {
+                                log::warn!("Error sending html to channel: {:?}", e);
+                            }
+                        }
+                        None => log::warn!("Got 304 for {} with no cached entry", url_str),
+                    }
+                    return;
+                }
+
+                let response = match response.error_for_status() {
+                    Ok(res) => res,
                     Err(e) => {
                         log::warn!("URL {:?} returned status {:?}", e.url(), e.status());
                         return;
                     }
                 };
 
+                // A redirect moved us to a canonical URL; key the visited-set on
+                // it so the same content is not crawled again under its source.
+                if final_url.as_str() != url_str {
+                    log::info!("Crawled {} via redirect to {}", url_str, final_url);
+                    let path = final_url.path().trim_end_matches('/').to_owned();
+                    self.visited_paths.lock().await.insert(path);
+                }
+
+                let cache_control = CacheControl::parse(response.headers());
+                let headers = response.headers().clone();
                 let html = match response.text().await {
                     Ok(html) => html,
                     Err(e) => {
@@ -55,7 +203,15 @@ impl Requester {
                         return;
                     }
                 };
-                match self.html_tx.send_async(html).await {
+
+                if cache_control.is_cachable() {
+                    let entry = CacheEntry::from_response(&headers, html.clone());
+                    if let Err(e) = self.cache.put(&url_str, &entry) {
+                        log::warn!("Error writing cache for {}: {:?}", url_str, e);
+                    }
+                }
+
+                match self.html_tx.send_async((final_url, html)).await {
                     Ok(_) => (),
                     Err(e) => log::warn!("Error sending html to channel: {:?}", e),
                 }
@@ -63,6 +219,21 @@ impl Requester {
             .await;
         Ok(())
     }
+
+    /// Emits a [`CrawlEvent::Visited`] in JSON output mode.
+    fn emit_visited(&self, url: &Url, status: reqwest::StatusCode, elapsed: Duration) {
+        if self.output != OutputMode::Json {
+            return;
+        }
+        let event = CrawlEvent::Visited {
+            url: url.clone(),
+            status: status.as_u16(),
+            elapsed_ms: elapsed.as_millis(),
+        };
+        if let Err(e) = self.event_tx.send(event) {
+            log::warn!("Error sending crawl event: {:?}", e);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -70,8 +241,10 @@ mod tests {
     use super::*;
 
     mod run {
+        use std::{collections::HashSet, sync::Arc};
+
         use super::*;
-        use tokio::join;
+        use tokio::{join, sync::Mutex};
 
         #[tokio::test]
         async fn basic() {
@@ -85,11 +258,25 @@ mod tests {
 
             let (url_tx, url_rx) = flume::unbounded();
             let (html_tx, html_rx) = flume::unbounded();
+            let (event_tx, _event_rx) = flume::unbounded();
 
             url_tx.send(url).unwrap();
             drop(url_tx);
 
-            let requester = Requester::new(url_rx, html_tx, 1, Duration::from_secs(1));
+            let visited: AtomicSet = Arc::new(Mutex::new(HashSet::new()));
+            let requester = Requester::new(
+                url_rx,
+                html_tx,
+                event_tx,
+                OutputMode::Human,
+                1,
+                Duration::from_secs(1),
+                10,
+                "crawl-rs/test".to_owned(),
+                visited,
+                RobotsGate::new("crawl-rs/test".to_owned(), false, Duration::from_secs(1)),
+                RateLimiter::new(Duration::ZERO),
+            );
             let requester_handle = tokio::spawn(async move {
                 if let Err(e) = requester.run().await {
                     log::error!("{}", e);
@@ -100,11 +287,56 @@ mod tests {
                 if htmls.len() == 1 {
                     break;
                 }
-                htmls.push(html_rx.recv_async().await.unwrap());
+                htmls.push(html_rx.recv_async().await.unwrap().1);
             }
 
             join!(requester_handle).0.unwrap();
             assert_eq!(htmls, vec![html]);
         }
+
+        #[tokio::test]
+        async fn follows_redirect() {
+            let url = Url::parse(&mockito::server_url()).unwrap();
+            let _redirect = mockito::mock("GET", "/")
+                .with_status(301)
+                .with_header("location", "/final")
+                .create();
+            let _final = mockito::mock("GET", "/final")
+                .with_status(200)
+                .with_header("content-type", "text/html; charset=UTF-8")
+                .with_body("<html></html>")
+                .create();
+
+            let (url_tx, url_rx) = flume::unbounded();
+            let (html_tx, html_rx) = flume::unbounded();
+            let (event_tx, _event_rx) = flume::unbounded();
+
+            url_tx.send(url).unwrap();
+            drop(url_tx);
+
+            let visited: AtomicSet = Arc::new(Mutex::new(HashSet::new()));
+            let requester = Requester::new(
+                url_rx,
+                html_tx,
+                event_tx,
+                OutputMode::Human,
+                1,
+                Duration::from_secs(1),
+                10,
+                "crawl-rs/test".to_owned(),
+                Arc::clone(&visited),
+                RobotsGate::new("crawl-rs/test".to_owned(), false, Duration::from_secs(1)),
+                RateLimiter::new(Duration::ZERO),
+            );
+            let requester_handle = tokio::spawn(async move {
+                requester.run().await.unwrap();
+            });
+
+            let (_final_url, html) = html_rx.recv_async().await.unwrap();
+            join!(requester_handle).0.unwrap();
+
+            assert_eq!("<html></html>", html);
+            assert!(visited.lock().await.contains("/final"));
+        }
     }
 }
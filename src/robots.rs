@@ -0,0 +1,220 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use tokio::sync::Mutex;
+use url::Url;
+
+/// Parsed `robots.txt` rules for a single applicable user-agent group.
+#[derive(Debug, Clone, Default)]
+pub struct Rules {
+    disallow: Vec<String>,
+    allow: Vec<String>,
+    crawl_delay: Option<Duration>,
+}
+
+impl Rules {
+    /// Parses `robots.txt` `body`, selecting the rule group that best matches
+    /// `user_agent` (falling back to the `*` group).
+    pub fn parse(body: &str, user_agent: &str) -> Self {
+        let mut groups: Vec<(Vec<String>, Rules)> = Vec::new();
+        let mut starting_group = true;
+
+        for line in body.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field.trim().to_ascii_lowercase(), value.trim()),
+                None => continue,
+            };
+            match field.as_str() {
+                "user-agent" => {
+                    // Consecutive user-agent lines share the following rules.
+                    if starting_group || groups.is_empty() {
+                        groups.push((Vec::new(), Rules::default()));
+                    }
+                    if let Some((agents, _)) = groups.last_mut() {
+                        agents.push(value.to_ascii_lowercase());
+                    }
+                    starting_group = false;
+                }
+                "disallow" | "allow" | "crawl-delay" => {
+                    starting_group = true;
+                    if let Some((_, rules)) = groups.last_mut() {
+                        match field.as_str() {
+                            "disallow" if !value.is_empty() => rules.disallow.push(value.to_owned()),
+                            "allow" if !value.is_empty() => rules.allow.push(value.to_owned()),
+                            "crawl-delay" => {
+                                rules.crawl_delay =
+                                    value.parse::<f64>().ok().map(Duration::from_secs_f64);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let user_agent = user_agent.to_ascii_lowercase();
+        select_group(&groups, &user_agent).unwrap_or_default()
+    }
+
+    /// Whether `path` may be crawled. The longest matching `Allow`/`Disallow`
+    /// directive wins, per the de-facto standard.
+    pub fn allows(&self, path: &str) -> bool {
+        match (longest_match(&self.allow, path), longest_match(&self.disallow, path)) {
+            (Some(allow), Some(disallow)) => allow >= disallow,
+            (None, Some(_)) => false,
+            _ => true,
+        }
+    }
+
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+}
+
+/// Picks the most specific group matching `user_agent`, preferring a named
+/// token over the catch-all `*`.
+fn select_group(groups: &[(Vec<String>, Rules)], user_agent: &str) -> Option<Rules> {
+    let mut wildcard = None;
+    let mut best: Option<(usize, &Rules)> = None;
+    for (agents, rules) in groups {
+        for agent in agents {
+            if agent == "*" {
+                wildcard = Some(rules.clone());
+            } else if user_agent.starts_with(agent.as_str())
+                && best.map_or(true, |(len, _)| agent.len() > len)
+            {
+                best = Some((agent.len(), rules));
+            }
+        }
+    }
+    best.map(|(_, rules)| rules.clone()).or(wildcard)
+}
+
+fn longest_match(patterns: &[String], path: &str) -> Option<usize> {
+    patterns
+        .iter()
+        .filter(|pattern| path.starts_with(pattern.as_str()))
+        .map(|pattern| pattern.len())
+        .max()
+}
+
+/// Fetches and caches `robots.txt` rules per host and answers crawl queries.
+#[derive(Clone)]
+pub struct RobotsGate {
+    client: reqwest::Client,
+    user_agent: String,
+    respect: bool,
+    cache: Arc<Mutex<HashMap<String, Rules>>>,
+}
+
+impl RobotsGate {
+    pub fn new(user_agent: String, respect: bool, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(user_agent.clone())
+            .timeout(timeout)
+            .build()
+            .unwrap();
+        Self {
+            client,
+            user_agent,
+            respect,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Whether `url` may be crawled under its host's `robots.txt`.
+    pub async fn is_allowed(&self, url: &Url) -> bool {
+        if !self.respect {
+            return true;
+        }
+        match url.host_str() {
+            Some(host) => self.rules_for(host, url).await.allows(url.path()),
+            None => true,
+        }
+    }
+
+    /// The `Crawl-delay` advertised for `url`'s host, if any.
+    pub async fn crawl_delay(&self, url: &Url) -> Option<Duration> {
+        if !self.respect {
+            return None;
+        }
+        let host = url.host_str()?;
+        self.rules_for(host, url).await.crawl_delay()
+    }
+
+    /// Returns the cached rules for `host`, fetching `robots.txt` once on miss.
+    /// The cache lock is held across the fetch so concurrent first hits to the
+    /// same host share a single `robots.txt` request.
+    async fn rules_for(&self, host: &str, url: &Url) -> Rules {
+        let mut cache = self.cache.lock().await;
+        if let Some(rules) = cache.get(host) {
+            return rules.clone();
+        }
+        let rules = match url.join("/robots.txt") {
+            Ok(robots_url) => match self.client.get(robots_url).send().await {
+                Ok(response) => match response.text().await {
+                    Ok(body) => Rules::parse(&body, &self.user_agent),
+                    Err(e) => {
+                        log::warn!("Error reading robots.txt for {}: {:?}", host, e);
+                        Rules::default()
+                    }
+                },
+                Err(e) => {
+                    log::warn!("Error fetching robots.txt for {}: {:?}", host, e);
+                    Rules::default()
+                }
+            },
+            Err(e) => {
+                log::warn!("Could not build robots.txt url for {}: {:?}", host, e);
+                Rules::default()
+            }
+        };
+        cache.insert(host.to_owned(), rules.clone());
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod rules {
+        use super::*;
+
+        const ROBOTS: &str = "User-agent: *\n\
+            Disallow: /private\n\
+            Allow: /private/public\n\
+            Crawl-delay: 2\n\
+            \n\
+            User-agent: crawl-rs\n\
+            Disallow: /secret\n";
+
+        #[test]
+        fn wildcard_group_disallows_prefix() {
+            let rules = Rules::parse(ROBOTS, "some-other-bot");
+            assert!(!rules.allows("/private/page"));
+            assert!(rules.allows("/public"));
+            assert_eq!(Some(Duration::from_secs(2)), rules.crawl_delay());
+        }
+
+        #[test]
+        fn longer_allow_overrides_disallow() {
+            let rules = Rules::parse(ROBOTS, "some-other-bot");
+            assert!(rules.allows("/private/public/doc"));
+        }
+
+        #[test]
+        fn named_group_is_preferred() {
+            let rules = Rules::parse(ROBOTS, "crawl-rs/0.1.0");
+            assert!(!rules.allows("/secret"));
+            assert!(rules.allows("/private"));
+        }
+
+        #[test]
+        fn empty_robots_allows_everything() {
+            let rules = Rules::parse("", "crawl-rs");
+            assert!(rules.allows("/anything"));
+        }
+    }
+}
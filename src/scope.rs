@@ -0,0 +1,71 @@
+use clap::ValueEnum;
+use url::Url;
+
+/// How far from its seed host a crawl is allowed to wander.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Scope {
+    /// Only the exact seed host (e.g. `community.monzo.com`).
+    Host,
+    /// Any host ending in the seed's registrable domain.
+    Subdomains,
+    /// Any host sharing the seed's registrable domain.
+    Domain,
+}
+
+impl Scope {
+    /// Whether `candidate` is in scope for a crawl seeded at `seed_host`.
+    pub fn matches(&self, seed_host: &str, candidate: &Url) -> bool {
+        let candidate_host = match candidate.host_str() {
+            Some(host) => host,
+            None => return false,
+        };
+        match self {
+            Scope::Host => candidate_host == seed_host,
+            Scope::Subdomains => {
+                let registrable = registrable_domain(seed_host);
+                candidate_host == registrable
+                    || candidate_host.ends_with(&format!(".{registrable}"))
+            }
+            Scope::Domain => registrable_domain(candidate_host) == registrable_domain(seed_host),
+        }
+    }
+}
+
+/// The registrable (eTLD+1) domain of `host`, falling back to the host itself
+/// when the public suffix list does not recognise it.
+fn registrable_domain(host: &str) -> String {
+    psl::domain_str(host).unwrap_or(host).to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn host_is_exact() {
+        let scope = Scope::Host;
+        assert!(scope.matches("community.monzo.com", &url("https://community.monzo.com/c/5")));
+        assert!(!scope.matches("community.monzo.com", &url("https://www.community.monzo.com/")));
+        assert!(!scope.matches("community.monzo.com", &url("https://monzo.com/blog")));
+    }
+
+    #[test]
+    fn subdomains_keeps_hosts_under_registrable_domain() {
+        let scope = Scope::Subdomains;
+        assert!(scope.matches("community.monzo.com", &url("https://www.community.monzo.com/")));
+        assert!(scope.matches("community.monzo.com", &url("https://monzo.com/blog")));
+        assert!(!scope.matches("community.monzo.com", &url("https://example.com/")));
+    }
+
+    #[test]
+    fn domain_matches_shared_registrable_domain() {
+        let scope = Scope::Domain;
+        assert!(scope.matches("monzo.com", &url("https://community.monzo.com/")));
+        assert!(scope.matches("community.monzo.com", &url("https://monzo.com/")));
+        assert!(!scope.matches("monzo.com", &url("https://monzo.co.uk/")));
+    }
+}